@@ -0,0 +1,3 @@
+-- Track views so pastes can expire after being read a set number of times
+ALTER TABLE pastes ADD COLUMN views INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE pastes ADD COLUMN max_views INTEGER;
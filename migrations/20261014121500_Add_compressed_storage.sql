@@ -0,0 +1,3 @@
+-- Pastes can optionally be stored zstd-compressed
+ALTER TABLE pastes ADD COLUMN compressed BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE pastes ADD COLUMN compressed_contents BYTEA;
@@ -0,0 +1,4 @@
+-- Pastes can opt in to being listed in the public feed
+ALTER TABLE pastes ADD COLUMN public BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE pastes ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT now();
+CREATE INDEX pastes_public_created_at ON pastes (created_at) WHERE public;
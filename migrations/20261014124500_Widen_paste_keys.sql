@@ -0,0 +1,2 @@
+-- Keys can have an operator configured prefix in front of them
+ALTER TABLE pastes ALTER COLUMN key TYPE VARCHAR(64);
@@ -0,0 +1,3 @@
+-- Pastes can optionally be encrypted at rest with a server side key
+ALTER TABLE pastes ADD COLUMN encrypted_contents BYTEA;
+ALTER TABLE pastes ADD COLUMN nonce BYTEA;
@@ -0,0 +1,2 @@
+-- Pastes can optionally be given a title
+ALTER TABLE pastes ADD COLUMN title TEXT;
@@ -0,0 +1,5 @@
+-- Store the size of each paste so it doesn't have to be computed on every read
+ALTER TABLE pastes ADD COLUMN size_bytes INTEGER;
+-- Compressed and encrypted pastes can't be measured from SQL, so their stored size is used instead
+UPDATE pastes SET size_bytes = COALESCE(octet_length(contents), octet_length(compressed_contents), octet_length(encrypted_contents), 0);
+ALTER TABLE pastes ALTER COLUMN size_bytes SET NOT NULL;
@@ -0,0 +1,2 @@
+-- How ANSI escape codes were handled when the paste was rendered
+ALTER TABLE pastes ADD COLUMN render_mode TEXT NOT NULL DEFAULT 'plain';
@@ -0,0 +1,7 @@
+-- Tags creators attach to their pastes, listed at /tag/:tag for public pastes
+CREATE TABLE paste_tags (
+    paste_key VARCHAR(64) NOT NULL REFERENCES pastes (key) ON DELETE CASCADE,
+    tag VARCHAR(32) NOT NULL,
+    PRIMARY KEY (paste_key, tag)
+);
+CREATE INDEX paste_tags_tag ON paste_tags (tag);
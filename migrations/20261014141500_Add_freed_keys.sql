@@ -0,0 +1,5 @@
+-- Keys of deleted pastes, held back from new pastes for key_reuse_cooldown_days
+CREATE TABLE freed_keys (
+    key VARCHAR(64) PRIMARY KEY,
+    freed_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
@@ -0,0 +1,3 @@
+-- The filename a paste was uploaded as, and the language it is highlighted as
+ALTER TABLE pastes ADD COLUMN filename TEXT;
+ALTER TABLE pastes ADD COLUMN language TEXT;
@@ -0,0 +1,4 @@
+-- Who submitted a paste, only stored when store_creator_info is enabled
+ALTER TABLE pastes ADD COLUMN creator_user_agent TEXT;
+ALTER TABLE pastes ADD COLUMN creator_referrer TEXT;
+ALTER TABLE pastes ADD COLUMN creator_ip TEXT;
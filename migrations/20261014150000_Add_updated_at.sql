@@ -0,0 +1,2 @@
+-- When a paste's creator last edited it, NULL if it never was
+ALTER TABLE pastes ADD COLUMN updated_at TIMESTAMPTZ;
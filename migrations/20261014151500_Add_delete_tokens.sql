@@ -0,0 +1,2 @@
+-- SHA-256 of the token given to a paste's creator for deleting it, NULL for pastes made before tokens existed
+ALTER TABLE pastes ADD COLUMN delete_token_hash TEXT;
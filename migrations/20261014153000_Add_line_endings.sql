@@ -0,0 +1,2 @@
+-- Whether the paste's lines ended in CRLF, so the raw view can give them back that way
+ALTER TABLE pastes ADD COLUMN crlf BOOLEAN NOT NULL DEFAULT false;
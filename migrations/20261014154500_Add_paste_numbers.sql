@@ -0,0 +1,2 @@
+-- Counts pastes for keys derived from a number with key_secret, instead of picked at random
+CREATE SEQUENCE paste_numbers;
@@ -0,0 +1,3 @@
+-- SHA-256 of the text of each paste, for links by content. Pastes stored before this have none
+ALTER TABLE pastes ADD COLUMN content_hash TEXT;
+CREATE INDEX pastes_content_hash ON pastes (content_hash);
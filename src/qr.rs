@@ -0,0 +1,455 @@
+//! Just enough of a QR code encoder for paste links: byte mode at error
+//! correction level M, in versions 1 to 9, which hold up to 180 bytes
+
+use std::fmt::Write;
+
+/// Error correction codewords per block, and how many blocks of how many data
+/// codewords there are, for each version at level M
+const BLOCKS: [(usize, [(usize, usize); 2]); 9] = [
+    (10, [(1, 16), (0, 0)]),
+    (16, [(1, 28), (0, 0)]),
+    (26, [(1, 44), (0, 0)]),
+    (18, [(2, 32), (0, 0)]),
+    (24, [(2, 43), (0, 0)]),
+    (16, [(4, 27), (0, 0)]),
+    (18, [(4, 31), (0, 0)]),
+    (22, [(2, 38), (2, 39)]),
+    (22, [(3, 36), (2, 37)]),
+];
+
+/// Rows and columns the alignment patterns are centered on, for each version
+const ALIGNMENT: [&[usize]; 9] = [
+    &[],
+    &[6, 18],
+    &[6, 22],
+    &[6, 26],
+    &[6, 30],
+    &[6, 34],
+    &[6, 22, 38],
+    &[6, 24, 42],
+    &[6, 26, 46],
+];
+
+/// White border around the code, in modules, that scanners need to find it
+const QUIET_ZONE: usize = 4;
+
+pub struct QrCode {
+    version: usize,
+    size: usize,
+    /// Row by row, true for dark modules
+    modules: Vec<bool>,
+    /// Modules that are part of the patterns rather than the data
+    function: Vec<bool>,
+}
+
+impl QrCode {
+    /// `None` if `data` is too long for version 9
+    pub fn encode(data: &[u8]) -> Option<Self> {
+        let fits = |(_, groups): &(usize, [(usize, usize); 2])| {
+            // Mode and length, then the data
+            4 + 8 + data.len() * 8 <= data_capacity(groups) * 8
+        };
+        let version = BLOCKS.iter().position(fits)? + 1;
+        let (ec_len, groups) = BLOCKS[version - 1];
+        let capacity = data_capacity(&groups);
+
+        let mut bits = Vec::new();
+        push_bits(&mut bits, 0b0100, 4);
+        push_bits(&mut bits, data.len() as u32, 8);
+        for byte in data {
+            push_bits(&mut bits, u32::from(*byte), 8);
+        }
+        let terminator = (capacity * 8 - bits.len()).min(4);
+        push_bits(&mut bits, 0, terminator);
+        let padding = (8 - bits.len() % 8) % 8;
+        push_bits(&mut bits, 0, padding);
+        let mut codewords: Vec<u8> = bits
+            .chunks(8)
+            .map(|byte| byte.iter().fold(0, |acc, bit| acc << 1 | u8::from(*bit)))
+            .collect();
+        for pad in [0xEC, 0x11].into_iter().cycle() {
+            if codewords.len() >= capacity {
+                break;
+            }
+            codewords.push(pad);
+        }
+
+        let divisor = rs_divisor(ec_len);
+        let mut blocks = Vec::new();
+        let mut rest = codewords.as_slice();
+        for (count, len) in groups {
+            for _ in 0..count {
+                let (block, remaining) = rest.split_at(len);
+                rest = remaining;
+                blocks.push((block, rs_remainder(block, &divisor)));
+            }
+        }
+        let longest = blocks.iter().map(|(block, _)| block.len()).max()?;
+        let mut interleaved = Vec::new();
+        for i in 0..longest {
+            interleaved.extend(blocks.iter().filter_map(|(block, _)| block.get(i)));
+        }
+        for i in 0..ec_len {
+            interleaved.extend(blocks.iter().map(|(_, ec)| ec[i]));
+        }
+
+        let size = 17 + 4 * version;
+        let mut qr = Self {
+            version,
+            size,
+            modules: vec![false; size * size],
+            function: vec![false; size * size],
+        };
+        qr.draw_function_patterns();
+        qr.draw_codewords(&interleaved);
+        // Masking twice undoes it, so each mask can be tried in place
+        let mask = (0..8).min_by_key(|&mask| {
+            qr.apply_mask(mask);
+            qr.draw_format(mask);
+            let penalty = qr.penalty();
+            qr.apply_mask(mask);
+            penalty
+        })?;
+        qr.apply_mask(mask);
+        qr.draw_format(mask);
+        Some(qr)
+    }
+
+    /// Black on white, one unit per module
+    pub fn to_svg(&self) -> String {
+        let mut path = String::new();
+        for y in 0..self.size {
+            for x in 0..self.size {
+                if self.get(x, y) {
+                    write!(path, "M{},{}h1v1h-1z", x + QUIET_ZONE, y + QUIET_ZONE).ok();
+                }
+            }
+        }
+        let dimension = self.size + QUIET_ZONE * 2;
+        format!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {0} {0}" shape-rendering="crispEdges"><rect width="{0}" height="{0}" fill="#fff"/><path d="{1}" fill="#000"/></svg>"##,
+            dimension, path
+        )
+    }
+
+    fn get(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.modules[y * self.size + x] = dark;
+        self.function[y * self.size + x] = true;
+    }
+
+    fn draw_function_patterns(&mut self) {
+        for i in 0..self.size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+        let far = self.size - 4;
+        for (x, y) in [(3, 3), (far, 3), (3, far)] {
+            self.draw_pattern(x, y, 4, |distance| distance != 2 && distance != 4);
+        }
+        let positions = ALIGNMENT[self.version - 1];
+        let last = positions.len().saturating_sub(1);
+        for (i, &x) in positions.iter().enumerate() {
+            for (j, &y) in positions.iter().enumerate() {
+                let on_finder = (i == 0 && (j == 0 || j == last)) || (i == last && j == 0);
+                if !on_finder {
+                    self.draw_pattern(x, y, 2, |distance| distance != 1);
+                }
+            }
+        }
+        // Reserves the format areas until the mask is known
+        self.draw_format(0);
+        if self.version >= 7 {
+            self.draw_version();
+        }
+    }
+
+    /// Draws the square of `radius` around a module, with each ring dark or
+    /// light by its distance from the center
+    fn draw_pattern(&mut self, x: usize, y: usize, radius: isize, dark: fn(isize) -> bool) {
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                let (xx, yy) = (x as isize + dx, y as isize + dy);
+                if (0..self.size as isize).contains(&xx) && (0..self.size as isize).contains(&yy) {
+                    self.set_function(xx as usize, yy as usize, dark(dx.abs().max(dy.abs())));
+                }
+            }
+        }
+    }
+
+    fn draw_format(&mut self, mask: u32) {
+        // Level M is 00, so only the mask is left
+        let data = mask;
+        let mut remainder = data;
+        for _ in 0..10 {
+            remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+        }
+        let bits = (data << 10 | remainder) ^ 0x5412;
+        let bit = |i: usize| bits >> i & 1 == 1;
+        let size = self.size;
+        for i in 0..=5 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+        self.set_function(8, size - 8, true);
+    }
+
+    fn draw_version(&mut self) {
+        let version = self.version as u32;
+        let mut remainder = version;
+        for _ in 0..12 {
+            remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1F25);
+        }
+        let bits = version << 12 | remainder;
+        for i in 0..18 {
+            let dark = bits >> i & 1 == 1;
+            let (a, b) = (self.size - 11 + i % 3, i / 3);
+            self.set_function(a, b, dark);
+            self.set_function(b, a, dark);
+        }
+    }
+
+    /// Fills the data modules two columns at a time, zigzagging up and down
+    /// from the bottom right
+    fn draw_codewords(&mut self, data: &[u8]) {
+        let mut i = 0;
+        let mut right = self.size - 1;
+        while right >= 1 {
+            // The vertical timing pattern takes up a whole column
+            if right == 6 {
+                right = 5;
+            }
+            let upward = (right + 1) & 2 == 0;
+            for vertical in 0..self.size {
+                let y = if upward {
+                    self.size - 1 - vertical
+                } else {
+                    vertical
+                };
+                for x in [right, right - 1] {
+                    if !self.function[y * self.size + x] && i < data.len() * 8 {
+                        self.modules[y * self.size + x] = data[i / 8] >> (7 - i % 8) & 1 == 1;
+                        i += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u32) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                if invert && !self.function[y * self.size + x] {
+                    self.modules[y * self.size + x] ^= true;
+                }
+            }
+        }
+    }
+
+    /// How hard the code is to scan, by the rules masks are chosen with
+    fn penalty(&self) -> usize {
+        let size = self.size;
+        let mut penalty = 0;
+        let lines = (0..size).flat_map(|i| {
+            [
+                (0..size).map(|j| self.get(j, i)).collect::<Vec<_>>(),
+                (0..size).map(|j| self.get(i, j)).collect::<Vec<_>>(),
+            ]
+        });
+        const FINDER_LIKE: [bool; 11] = [
+            true, false, true, true, true, false, true, false, false, false, false,
+        ];
+        for line in lines {
+            let mut run = 1;
+            for j in 1..=size {
+                if j < size && line[j] == line[j - 1] {
+                    run += 1;
+                    continue;
+                }
+                if run >= 5 {
+                    penalty += run - 2;
+                }
+                run = 1;
+            }
+            for window in line.windows(11) {
+                if window == FINDER_LIKE || window.iter().eq(FINDER_LIKE.iter().rev()) {
+                    penalty += 40;
+                }
+            }
+        }
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let color = self.get(x, y);
+                if color == self.get(x + 1, y)
+                    && color == self.get(x, y + 1)
+                    && color == self.get(x + 1, y + 1)
+                {
+                    penalty += 3;
+                }
+            }
+        }
+        let dark = self.modules.iter().filter(|dark| **dark).count();
+        let percent = dark * 100 / self.modules.len();
+        penalty + percent.abs_diff(50) / 5 * 10
+    }
+}
+
+fn data_capacity(groups: &[(usize, usize); 2]) -> usize {
+    groups.iter().map(|(count, len)| count * len).sum()
+}
+
+fn push_bits(bits: &mut Vec<bool>, value: u32, count: usize) {
+    bits.extend((0..count).rev().map(|i| value >> i & 1 == 1));
+}
+
+/// Multiplies in GF(2^8) modulo the polynomial QR codes use
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    let mut z: u16 = 0;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x11D);
+        z ^= u16::from(y >> i & 1) * u16::from(x);
+    }
+    z as u8
+}
+
+/// The Reed-Solomon generator polynomial of `degree`, highest term first and
+/// without its leading 1
+fn rs_divisor(degree: usize) -> Vec<u8> {
+    let mut result = vec![0; degree];
+    result[degree - 1] = 1;
+    let mut root = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_multiply(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+    result
+}
+
+/// Error correction codewords for `data`
+fn rs_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0; divisor.len()];
+    for byte in data {
+        let factor = byte ^ result.remove(0);
+        result.push(0);
+        for (remainder, coefficient) in result.iter_mut().zip(divisor) {
+            *remainder ^= gf_multiply(*coefficient, factor);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiplies_in_the_qr_field() {
+        assert_eq!(gf_multiply(0x8E, 0x01), 0x8E);
+        assert_eq!(gf_multiply(0x80, 0x02), 0x1D);
+        assert_eq!(gf_multiply(0x1D, 0x02), 0x3A);
+        assert_eq!(gf_multiply(0x80, 0x80), 0x13);
+        assert_eq!(gf_multiply(0xFF, 0x00), 0x00);
+    }
+
+    #[test]
+    fn generator_polynomials_match_the_spec() {
+        assert_eq!(rs_divisor(7), [127, 122, 154, 164, 11, 68, 117]);
+        assert_eq!(
+            rs_divisor(10),
+            [216, 194, 159, 111, 199, 94, 95, 113, 157, 193]
+        );
+    }
+
+    #[test]
+    fn error_correction_matches_hello_world() {
+        // The worked example for "HELLO WORLD" at 1-M
+        let data = [
+            32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+        ];
+        assert_eq!(
+            rs_remainder(&data, &rs_divisor(10)),
+            [196, 35, 39, 119, 235, 215, 231, 226, 93, 23]
+        );
+    }
+
+    #[test]
+    fn picks_the_smallest_version() {
+        let version = |len: usize| QrCode::encode(&vec![b'a'; len]).map(|qr| qr.version);
+        assert_eq!(version(0), Some(1));
+        assert_eq!(version(14), Some(1));
+        assert_eq!(version(15), Some(2));
+        assert_eq!(version(180), Some(9));
+        assert_eq!(version(181), None);
+    }
+
+    #[test]
+    fn format_and_version_bits_match_the_spec() {
+        let mut qr = QrCode::encode(b"").unwrap();
+        qr.draw_format(0);
+        let mut format = 0;
+        for i in 0..8 {
+            format |= u32::from(qr.get(qr.size - 1 - i, 8)) << i;
+        }
+        for i in 8..15 {
+            format |= u32::from(qr.get(8, qr.size - 15 + i)) << i;
+        }
+        // Level M with mask 0, from the spec's table
+        assert_eq!(format, 0b101010000010010);
+
+        let qr = QrCode::encode(&[b'a'; 120]).unwrap();
+        assert_eq!(qr.version, 7);
+        let mut version = 0;
+        for i in 0..18 {
+            version |= u32::from(qr.get(qr.size - 11 + i % 3, i / 3)) << i;
+        }
+        assert_eq!(version, 0b000111110010010100);
+    }
+
+    #[test]
+    fn draws_finders_in_the_corners() {
+        let qr = QrCode::encode(b"hello").unwrap();
+        assert_eq!(qr.size, 21);
+        for (x, y) in [(0, 0), (qr.size - 7, 0), (0, qr.size - 7)] {
+            for i in 0..7 {
+                assert!(qr.get(x + i, y) && qr.get(x, y + i));
+                assert!(qr.get(x + i, y + 6) && qr.get(x + 6, y + i));
+            }
+            assert!(!qr.get(x + 1, y + 1));
+            assert!(qr.get(x + 3, y + 3));
+        }
+        assert!(qr.to_svg().contains(r#"viewBox="0 0 29 29""#));
+    }
+}